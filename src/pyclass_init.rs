@@ -21,10 +21,14 @@ use std::{
 pub trait PyObjectInit<T>: Sized {
     /// # Safety
     /// - `subtype` must be a valid pointer to a type object of T or a subclass.
+    /// - `args` and `kwargs`, if non-null, must be valid pointers to a `PyTuple` and a `PyDict`
+    ///   respectively, borrowed for the duration of the call.
     unsafe fn into_new_object(
         self,
         py: Python,
         subtype: *mut PyTypeObject,
+        args: *mut ffi::PyObject,
+        kwargs: *mut ffi::PyObject,
     ) -> PyResult<*mut ffi::PyObject>;
     private_decl! {}
 }
@@ -37,40 +41,78 @@ impl<T: PyTypeInfo> PyObjectInit<T> for PyNativeTypeInitializer<T> {
         self,
         py: Python,
         subtype: *mut PyTypeObject,
+        args: *mut ffi::PyObject,
+        kwargs: *mut ffi::PyObject,
     ) -> PyResult<*mut ffi::PyObject> {
         let type_object = T::type_object_raw(py);
 
-        // HACK (due to FIXME below): PyBaseObject_Type's tp_new isn't happy with NULL arguments
-        if type_object == (&ffi::PyBaseObject_Type as *const _ as *mut _) {
+        // HACK: PyBaseObject_Type's tp_new isn't happy with non-empty arguments, since the
+        // default `object.__init__`/`object.__new__` pair rejects them unless the subtype
+        // overrides one but not the other. `PyType_GenericAlloc` doesn't take any arguments at
+        // all, so there is nothing to forward here regardless of what the caller passed in.
+        let obj = if type_object == (&ffi::PyBaseObject_Type as *const _ as *mut _) {
             let alloc = get_tp_alloc(subtype).unwrap_or(ffi::PyType_GenericAlloc);
             let obj = alloc(subtype, 0);
-            return if obj.is_null() {
-                Err(PyErr::api_call_failed(py))
-            } else {
-                Ok(obj)
-            };
-        }
+            if obj.is_null() {
+                return Err(PyErr::api_call_failed(py));
+            }
+            obj
+        } else {
+            #[cfg(Py_LIMITED_API)]
+            unreachable!("subclassing native types is not possible with the `abi3` feature");
 
-        #[cfg(Py_LIMITED_API)]
-        unreachable!("subclassing native types is not possible with the `abi3` feature");
+            #[cfg(not(Py_LIMITED_API))]
+            {
+                let obj = match (*type_object).tp_new {
+                    Some(newfunc) => newfunc(subtype, args, kwargs),
+                    None => {
+                        return Err(crate::exceptions::PyTypeError::new_err(
+                            "base type without tp_new",
+                        ))
+                    }
+                };
+                if obj.is_null() {
+                    return Err(PyErr::api_call_failed(py));
+                }
 
-        #[cfg(not(Py_LIMITED_API))]
-        {
-            match (*type_object).tp_new {
-                // FIXME: Call __new__ with actual arguments
-                Some(newfunc) => {
-                    let obj = newfunc(subtype, std::ptr::null_mut(), std::ptr::null_mut());
-                    if obj.is_null() {
-                        Err(PyErr::api_call_failed(py))
-                    } else {
-                        Ok(obj)
+                // Mirror CPython's `type_call`: many native types (e.g. `dict`, `list`) only
+                // actually consume `args`/`kwargs` in `tp_init`, not `tp_new`, which only
+                // allocates. Only do this when the caller actually opted in via
+                // `PyClassInitializer::with_new_args` (`args` non-null): unlike `tp_new`,
+                // `tp_init` implementations (e.g. `dict_init`) assume `args` is always a real
+                // tuple and dereference it unconditionally, so calling this with the default
+                // null `args` would segfault instead of being a no-op.
+                if !args.is_null() {
+                    if let Some(initfunc) = (*type_object).tp_init {
+                        if initfunc(obj, args, kwargs) < 0 {
+                            ffi::Py_DECREF(obj);
+                            return Err(PyErr::api_call_failed(py));
+                        }
                     }
                 }
-                None => Err(crate::exceptions::PyTypeError::new_err(
-                    "base type without tp_new",
-                )),
+
+                obj
             }
+        };
+
+        // This is the base-most initializer in the hierarchy, so it owns the one-time
+        // initialization of the shared borrow flag: every `#[pyclass(extends = ...)]` layer
+        // above this one reuses the same slot rather than writing it again.
+        //
+        /// Layout of a `PyCellBase` right after the native base's `tp_new` has returned, but
+        /// before the borrow flag has been initialized.
+        #[repr(C)]
+        struct PartiallyInitializedPyCellBase<T> {
+            _ob_base: T,
+            borrow_flag: MaybeUninit<Cell<BorrowFlag>>,
         }
+        let base: *mut PartiallyInitializedPyCellBase<T> = obj as _;
+        std::ptr::write(
+            (*base).borrow_flag.as_mut_ptr(),
+            Cell::new(BorrowFlag::UNUSED),
+        );
+
+        Ok(obj)
     }
 
     private_impl! {}
@@ -122,11 +164,23 @@ impl<T: PyTypeInfo> PyObjectInit<T> for PyNativeTypeInitializer<T> {
 ///  assert sub_sub_class.subname == 'sub'
 ///  assert sub_sub_class.subsubname == 'subsub'"#
 ///     );
+///
+///     // The borrow flag lives once, in the shared base slot, so a borrow taken through the
+///     // `SubSubClass` view of the object is visible through the `BaseClass` view of that very
+///     // same object too -- not just when checked again through the same reference.
+///     let cell: &PyCell<SubSubClass> = sub_sub_class.extract().unwrap();
+///     let base_view: &PyCell<BaseClass> = sub_sub_class.extract().unwrap();
+///     let guard = cell.borrow();
+///     assert!(base_view.try_borrow_mut().is_err());
+///     drop(guard);
+///     assert!(base_view.try_borrow_mut().is_ok());
 /// });
 /// ```
 pub struct PyClassInitializer<T: PyClass> {
     init: T,
     super_init: <T::BaseType as PyClassBaseType>::Initializer,
+    new_args: Option<(*mut ffi::PyObject, *mut ffi::PyObject)>,
+    post_init: Option<Box<dyn FnOnce(&PyCell<T>, Python) -> PyResult<()>>>,
 }
 
 impl<T: PyClass> PyClassInitializer<T> {
@@ -134,7 +188,215 @@ impl<T: PyClass> PyClassInitializer<T> {
     ///
     /// It is recommended to use `add_subclass` instead of this method for most usage.
     pub fn new(init: T, super_init: <T::BaseType as PyClassBaseType>::Initializer) -> Self {
-        Self { init, super_init }
+        Self {
+            init,
+            super_init,
+            new_args: None,
+            post_init: None,
+        }
+    }
+
+    /// Sets a fallible hook to run once the [`PyCell`] exists, but before it is returned to
+    /// Python.
+    ///
+    /// Unlike `#[new]` itself, the hook receives the live `&PyCell<T>`, so it can interact with
+    /// the fully-initialized instance (e.g. to register it somewhere, or validate invariants
+    /// against the now-live base class). If the hook returns `Err`, the freshly-created object
+    /// is dropped and the error is propagated to the caller instead of the new instance.
+    ///
+    /// Only a hook set on the outermost initializer -- the one actually returned by `#[new]` --
+    /// ever runs. Setting one earlier in an `add_subclass` chain (e.g. on the `BaseClass` layer
+    /// of a multi-level hierarchy) is an error: it can never fire, since every intermediate
+    /// layer's own `PyClassInitializer` is consumed by `add_subclass` well before the object
+    /// exists to hand to it. Attempting to construct such an object returns `Err` rather than
+    /// silently dropping the hook.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// # use pyo3::py_run;
+    /// # use pyo3::exceptions::PyValueError;
+    /// #[pyclass]
+    /// struct Widget {
+    ///     #[pyo3(get)]
+    ///     id: u32,
+    /// }
+    ///
+    /// #[pymethods]
+    /// impl Widget {
+    ///     #[new]
+    ///     fn new(id: u32) -> PyClassInitializer<Self> {
+    ///         PyClassInitializer::from(Widget { id }).with_post_init(move |_cell, _py| {
+    ///             if id == 0 {
+    ///                 return Err(PyValueError::new_err("id must be non-zero"));
+    ///             }
+    ///             Ok(())
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let typeobj = py.get_type::<Widget>();
+    ///     let widget = typeobj.call((1,), None).unwrap();
+    ///     py_run!(py, widget, "assert widget.id == 1");
+    ///     assert!(typeobj.call((0,), None).is_err());
+    /// });
+    /// ```
+    ///
+    /// Setting the hook anywhere but the outermost layer of a multi-level hierarchy is rejected,
+    /// since it would otherwise be silently discarded and never run:
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// #[pyclass(subclass)]
+    /// struct BaseClass {}
+    ///
+    /// #[pyclass(extends = BaseClass)]
+    /// struct SubClass {}
+    ///
+    /// #[pymethods]
+    /// impl SubClass {
+    ///     #[new]
+    ///     fn new() -> PyClassInitializer<Self> {
+    ///         PyClassInitializer::from(BaseClass {})
+    ///             .with_post_init(|_cell, _py| Ok(())) // wrong: not the final layer
+    ///             .add_subclass(SubClass {})
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let typeobj = py.get_type::<SubClass>();
+    ///     assert!(typeobj.call((), None).is_err());
+    /// });
+    /// ```
+    pub fn with_post_init<F>(mut self, post_init: F) -> Self
+    where
+        F: FnOnce(&PyCell<T>, Python) -> PyResult<()> + 'static,
+    {
+        self.post_init = Some(Box::new(post_init));
+        self
+    }
+
+    /// Sets the `args`/`kwargs` that should be forwarded to the base type's `tp_new`, instead
+    /// of the default of calling it with no arguments.
+    ///
+    /// This allows a `#[new]` method to hand its own `*args`/`**kwargs` (or an explicit tuple/dict
+    /// built in Rust) down to a native base type's constructor, e.g. so that
+    /// `#[pyclass(extends = PyDict)]` can be constructed with initial contents. Only the
+    /// outermost (most-derived) layer of a class hierarchy needs to call this: the arguments are
+    /// forwarded unchanged through every intermediate `#[pyclass(extends = ...)]` layer down to
+    /// the base-most native initializer, which passes them to both `tp_new` *and* `tp_init`
+    /// (mirroring CPython's `type_call`) since types like `dict`/`list` only actually populate
+    /// themselves in `tp_init`. When the base-most type is `object`, the arguments are not used:
+    /// the object is allocated with the type's `tp_alloc`, and neither `tp_new` nor `tp_init` is
+    /// called.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use pyo3::prelude::*;
+    /// # use pyo3::py_run;
+    /// # use pyo3::types::{IntoPyDict, PyDict, PyTuple};
+    /// #[pyclass(extends = PyDict)]
+    /// struct DictWithContents {}
+    ///
+    /// #[pymethods]
+    /// impl DictWithContents {
+    ///     #[new]
+    ///     #[args(args = "*", kwargs = "**")]
+    ///     fn new(args: &PyTuple, kwargs: Option<&PyDict>) -> PyClassInitializer<Self> {
+    ///         let kwargs = kwargs.map_or(std::ptr::null_mut(), |d| d.as_ptr());
+    ///         // Safety: `args` and `kwargs` are borrowed from this call's arguments, and stay
+    ///         // valid for the duration of the `tp_new` call that this initializer is returned to.
+    ///         unsafe {
+    ///             PyClassInitializer::from(DictWithContents {}).with_new_args(args.as_ptr(), kwargs)
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let contents = [("a", 1), ("b", 2)].into_py_dict(py);
+    ///     let typeobj = py.get_type::<DictWithContents>();
+    ///     let instance = typeobj.call((contents,), None).unwrap();
+    ///     py_run!(py, instance, "assert instance['a'] == 1 and instance['b'] == 2");
+    /// });
+    /// ```
+    ///
+    /// The same applies to `PyList`:
+    ///
+    /// ```rust
+    /// # use pyo3::prelude::*;
+    /// # use pyo3::py_run;
+    /// # use pyo3::types::{PyDict, PyList, PyTuple};
+    /// #[pyclass(extends = PyList)]
+    /// struct ListWithContents {}
+    ///
+    /// #[pymethods]
+    /// impl ListWithContents {
+    ///     #[new]
+    ///     #[args(args = "*", kwargs = "**")]
+    ///     fn new(args: &PyTuple, kwargs: Option<&PyDict>) -> PyClassInitializer<Self> {
+    ///         let kwargs = kwargs.map_or(std::ptr::null_mut(), |d| d.as_ptr());
+    ///         // Safety: `args` and `kwargs` are borrowed from this call's arguments, and stay
+    ///         // valid for the duration of the `tp_new` call that this initializer is returned to.
+    ///         unsafe {
+    ///             PyClassInitializer::from(ListWithContents {}).with_new_args(args.as_ptr(), kwargs)
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let contents = PyList::new(py, [1, 2, 3]);
+    ///     let typeobj = py.get_type::<ListWithContents>();
+    ///     let instance = typeobj.call((contents,), None).unwrap();
+    ///     py_run!(py, instance, "assert list(instance) == [1, 2, 3]");
+    /// });
+    /// ```
+    ///
+    /// Without `with_new_args`, construction works as before: the base type's `tp_new` is called
+    /// with null `args`/`kwargs`, and its `tp_init` is not called at all:
+    ///
+    /// ```rust
+    /// # use pyo3::prelude::*;
+    /// # use pyo3::py_run;
+    /// # use pyo3::types::PyDict;
+    /// #[pyclass(extends = PyDict)]
+    /// struct PlainDict {}
+    ///
+    /// #[pymethods]
+    /// impl PlainDict {
+    ///     #[new]
+    ///     fn new() -> PyClassInitializer<Self> {
+    ///         PyClassInitializer::from(PlainDict {})
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let typeobj = py.get_type::<PlainDict>();
+    ///     let instance = typeobj.call((), None).unwrap();
+    ///     py_run!(py, instance, "assert dict(instance) == {}");
+    /// });
+    /// ```
+    ///
+    /// FIXME: the proc-macro-generated `tp_new` does not yet capture a `#[new]` method's
+    /// incoming arguments and call `with_new_args` automatically -- callers must thread
+    /// `args`/`kwargs` through by hand as shown above. That macro-side wiring (in
+    /// `pyo3-macros-backend`) has not landed yet, so this is not the ergonomic, opt-in-by-default
+    /// feature the original request describes, only the library-level building block for it.
+    ///
+    /// # Safety
+    /// `args` and `kwargs`, if non-null, must be valid pointers to a `PyTuple` and a `PyDict`
+    /// respectively, borrowed for the duration of the call to [`create_cell`](Self::create_cell)
+    /// or [`create_cell_from_subtype`](Self::create_cell_from_subtype).
+    #[doc(hidden)]
+    pub unsafe fn with_new_args(
+        mut self,
+        args: *mut ffi::PyObject,
+        kwargs: *mut ffi::PyObject,
+    ) -> Self {
+        self.new_args = Some((args, kwargs));
+        self
     }
 
     /// Constructs a new initializer from an initializer for the base class.
@@ -175,6 +437,10 @@ impl<T: PyClass> PyClassInitializer<T> {
     }
 
     /// Creates a new PyCell and initializes it.
+    ///
+    /// For a native base type other than `object`, the base type's `tp_new` is called with the
+    /// `args`/`kwargs` set via [`with_new_args`](Self::with_new_args), or with no arguments if
+    /// none were set. An `object` base is allocated with `tp_alloc` and takes no arguments.
     #[doc(hidden)]
     pub fn create_cell(self, py: Python) -> PyResult<*mut PyCell<T>>
     where
@@ -186,18 +452,35 @@ impl<T: PyClass> PyClassInitializer<T> {
     /// Creates a new PyCell and initializes it given a typeobject `subtype`.
     /// Called by the Python `tp_new` implementation generated by a `#[new]` function in a `#[pymethods]` block.
     ///
+    /// If a [`with_post_init`](Self::with_post_init) hook was set, it is run once the cell has
+    /// been fully initialized; if it fails, the newly-created cell is dropped and the error is
+    /// returned instead.
+    ///
     /// # Safety
     /// `subtype` must be a valid pointer to the type object of T or a subclass.
     #[doc(hidden)]
     pub unsafe fn create_cell_from_subtype(
-        self,
+        mut self,
         py: Python,
         subtype: *mut crate::ffi::PyTypeObject,
     ) -> PyResult<*mut PyCell<T>>
     where
         T: PyClass,
     {
-        self.into_new_object(py, subtype).map(|obj| obj as _)
+        let (args, kwargs) = self
+            .new_args
+            .unwrap_or((std::ptr::null_mut(), std::ptr::null_mut()));
+        let post_init = self.post_init.take();
+        let cell = self.into_new_object(py, subtype, args, kwargs)? as *mut PyCell<T>;
+
+        if let Some(post_init) = post_init {
+            if let Err(e) = post_init(&*cell, py) {
+                ffi::Py_DECREF(cell as *mut ffi::PyObject);
+                return Err(e);
+            }
+        }
+
+        Ok(cell)
     }
 }
 
@@ -206,15 +489,9 @@ impl<T: PyClass> PyObjectInit<T> for PyClassInitializer<T> {
         self,
         py: Python,
         subtype: *mut PyTypeObject,
+        args: *mut ffi::PyObject,
+        kwargs: *mut ffi::PyObject,
     ) -> PyResult<*mut ffi::PyObject> {
-        /// Layout of a PyCellBase after base new has been called, but the borrow flag has not
-        /// yet been initialized.
-        #[repr(C)]
-        struct PartiallyInitializedPyCellBase<T> {
-            _ob_base: T,
-            borrow_flag: MaybeUninit<Cell<BorrowFlag>>,
-        }
-
         /// Layout of a PyCell after base new has been called, but the contents have not yet been
         /// written.
         #[repr(C)]
@@ -223,17 +500,28 @@ impl<T: PyClass> PyObjectInit<T> for PyClassInitializer<T> {
             contents: MaybeUninit<PyCellContents<T>>,
         }
 
-        let Self { init, super_init } = self;
-        let obj = super_init.into_new_object(py, subtype)?;
-
-        // FIXME: Only need to initialize borrow flag once per whole hierarchy
-        let base: *mut PartiallyInitializedPyCellBase<T::BaseNativeType> = obj as _;
-        std::ptr::write(
-            (*base).borrow_flag.as_mut_ptr(),
-            Cell::new(BorrowFlag::UNUSED),
-        );
+        let Self {
+            init,
+            super_init,
+            new_args: _,
+            post_init,
+        } = self;
+        // A hook set here would never run: `create_cell`/`create_cell_from_subtype` only ever
+        // takes the post-init hook off the outermost initializer (the one `#[new]` returns),
+        // before recursing down into layers like this one. Silently dropping it would hide a
+        // hook the caller thought they'd registered, so fail loudly instead.
+        if post_init.is_some() {
+            return Err(crate::exceptions::PyRuntimeError::new_err(
+                "`with_post_init` was set on a `PyClassInitializer` that is not the outermost \
+                 layer returned by `#[new]`, so it would never run; call `with_post_init` on \
+                 the final initializer instead",
+            ));
+        }
+        // Only the base-most (native) initializer actually consumes `args`/`kwargs`, and it's
+        // also the one that initializes the borrow flag shared by the whole hierarchy; this
+        // layer only needs to write its own `PyCellContents`.
+        let obj = super_init.into_new_object(py, subtype, args, kwargs)?;
 
-        // FIXME: Initialize borrow flag if necessary??
         let cell: *mut PartiallyInitializedPyCell<T> = obj as _;
         std::ptr::write(
             (*cell).contents.as_mut_ptr(),